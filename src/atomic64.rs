@@ -12,10 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::Cell;
 use std::cmp::*;
 use std::f64;
+use std::mem::{size_of, transmute_copy};
 use std::ops::*;
-use std::sync::atomic::{AtomicI64 as StdAtomicI64, AtomicU64 as StdAtomicU64, Ordering};
+use std::sync::atomic::{
+    AtomicBool, AtomicI32 as StdAtomicI32, AtomicU16 as StdAtomicU16, AtomicU32 as StdAtomicU32,
+    AtomicU8 as StdAtomicU8, Ordering,
+};
+#[cfg(target_has_atomic = "64")]
+use std::sync::atomic::{AtomicI64 as StdAtomicI64, AtomicU64 as StdAtomicU64};
+#[cfg(not(target_has_atomic = "64"))]
+use std::sync::RwLock;
 
 /// An interface for numbers. Used to generically model float metrics and integer metrics, i.e.
 /// [`Counter`](::Counter) and [`IntCounter`](::IntCounter).
@@ -64,6 +73,42 @@ impl Number for f64 {
     }
 }
 
+impl Number for i32 {
+    #[inline]
+    fn from_i64(v: i64) -> Self {
+        v as i32
+    }
+
+    #[inline]
+    fn into_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Number for u32 {
+    #[inline]
+    fn from_i64(v: i64) -> Self {
+        v as u32
+    }
+
+    #[inline]
+    fn into_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Number for f32 {
+    #[inline]
+    fn from_i64(v: i64) -> Self {
+        v as f32
+    }
+
+    #[inline]
+    fn into_f64(self) -> f64 {
+        self as f64
+    }
+}
+
 /// An interface for atomics. Used to generically model float metrics and integer metrics, i.e.
 /// [`Counter`](::Counter) and [`IntCounter`](::IntCounter).
 pub trait Atomic: Send + Sync {
@@ -79,23 +124,31 @@ pub trait Atomic: Send + Sync {
     fn inc_by(&self, delta: Self::T);
     /// Decrement the value by a given amount.
     fn dec_by(&self, delta: Self::T);
+    /// Set the value to `val` if `val` is greater than the current value.
+    fn set_max(&self, val: Self::T);
+    /// Set the value to `val` if `val` is less than the current value.
+    fn set_min(&self, val: Self::T);
 }
 
 /// A atomic float.
+#[cfg(target_has_atomic = "64")]
 pub struct AtomicF64 {
     inner: StdAtomicU64,
 }
 
+#[cfg(target_has_atomic = "64")]
 #[inline]
 fn u64_to_f64(val: u64) -> f64 {
     f64::from_bits(val)
 }
 
+#[cfg(target_has_atomic = "64")]
 #[inline]
 fn f64_to_u64(val: f64) -> u64 {
     f64::to_bits(val)
 }
 
+#[cfg(target_has_atomic = "64")]
 impl Atomic for AtomicF64 {
     type T = f64;
 
@@ -117,15 +170,182 @@ impl Atomic for AtomicF64 {
 
     #[inline]
     fn inc_by(&self, delta: Self::T) {
+        let mut current = self.inner.load(Ordering::Acquire);
         loop {
-            let current = self.inner.load(Ordering::Acquire);
             let new = u64_to_f64(current) + delta;
-            let swapped = self
-                .inner
-                .compare_and_swap(current, f64_to_u64(new), Ordering::Release);
-            if swapped == current {
+            match self.inner.compare_exchange_weak(
+                current,
+                f64_to_u64(new),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(observed) => current = observed,
+            }
+        }
+    }
+
+    #[inline]
+    fn dec_by(&self, delta: Self::T) {
+        self.inc_by(-delta);
+    }
+
+    #[inline]
+    fn set_max(&self, val: Self::T) {
+        if val.is_nan() {
+            return;
+        }
+        let mut current = self.inner.load(Ordering::Relaxed);
+        loop {
+            if u64_to_f64(current) >= val {
                 return;
             }
+            match self.inner.compare_exchange_weak(
+                current,
+                f64_to_u64(val),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(observed) => current = observed,
+            }
+        }
+    }
+
+    #[inline]
+    fn set_min(&self, val: Self::T) {
+        if val.is_nan() {
+            return;
+        }
+        let mut current = self.inner.load(Ordering::Relaxed);
+        loop {
+            if u64_to_f64(current) <= val {
+                return;
+            }
+            match self.inner.compare_exchange_weak(
+                current,
+                f64_to_u64(val),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(observed) => current = observed,
+            }
+        }
+    }
+}
+
+/// A lock-based fallback for `AtomicF64` on targets without a native 64-bit
+/// atomic (e.g. some `mips`, `powerpc`, and embedded targets). `get` takes a
+/// read lock; `set`/`inc_by`/`dec_by` take a write lock and mutate in place.
+/// This is considerably slower under contention than the lock-free backend,
+/// but keeps the crate buildable everywhere with an identical public API.
+#[cfg(not(target_has_atomic = "64"))]
+pub struct AtomicF64 {
+    inner: RwLock<f64>,
+}
+
+#[cfg(not(target_has_atomic = "64"))]
+impl Atomic for AtomicF64 {
+    type T = f64;
+
+    fn new(val: Self::T) -> AtomicF64 {
+        AtomicF64 {
+            inner: RwLock::new(val),
+        }
+    }
+
+    #[inline]
+    fn set(&self, val: Self::T) {
+        *self.inner.write().unwrap() = val;
+    }
+
+    #[inline]
+    fn get(&self) -> Self::T {
+        *self.inner.read().unwrap()
+    }
+
+    #[inline]
+    fn inc_by(&self, delta: Self::T) {
+        *self.inner.write().unwrap() += delta;
+    }
+
+    #[inline]
+    fn dec_by(&self, delta: Self::T) {
+        *self.inner.write().unwrap() -= delta;
+    }
+
+    #[inline]
+    fn set_max(&self, val: Self::T) {
+        if val.is_nan() {
+            return;
+        }
+        let mut guard = self.inner.write().unwrap();
+        if val > *guard {
+            *guard = val;
+        }
+    }
+
+    #[inline]
+    fn set_min(&self, val: Self::T) {
+        if val.is_nan() {
+            return;
+        }
+        let mut guard = self.inner.write().unwrap();
+        if val < *guard {
+            *guard = val;
+        }
+    }
+}
+
+/// A atomic 32-bit float.
+pub struct AtomicF32 {
+    inner: StdAtomicU32,
+}
+
+#[inline]
+fn u32_to_f32(val: u32) -> f32 {
+    f32::from_bits(val)
+}
+
+#[inline]
+fn f32_to_u32(val: f32) -> u32 {
+    f32::to_bits(val)
+}
+
+impl Atomic for AtomicF32 {
+    type T = f32;
+
+    fn new(val: Self::T) -> AtomicF32 {
+        AtomicF32 {
+            inner: StdAtomicU32::new(f32_to_u32(val)),
+        }
+    }
+
+    #[inline]
+    fn set(&self, val: Self::T) {
+        self.inner.store(f32_to_u32(val), Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn get(&self) -> Self::T {
+        u32_to_f32(self.inner.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    fn inc_by(&self, delta: Self::T) {
+        let mut current = self.inner.load(Ordering::Acquire);
+        loop {
+            let new = u32_to_f32(current) + delta;
+            match self.inner.compare_exchange_weak(
+                current,
+                f32_to_u32(new),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(observed) => current = observed,
+            }
         }
     }
 
@@ -133,13 +353,59 @@ impl Atomic for AtomicF64 {
     fn dec_by(&self, delta: Self::T) {
         self.inc_by(-delta);
     }
+
+    #[inline]
+    fn set_max(&self, val: Self::T) {
+        if val.is_nan() {
+            return;
+        }
+        let mut current = self.inner.load(Ordering::Relaxed);
+        loop {
+            if u32_to_f32(current) >= val {
+                return;
+            }
+            match self.inner.compare_exchange_weak(
+                current,
+                f32_to_u32(val),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(observed) => current = observed,
+            }
+        }
+    }
+
+    #[inline]
+    fn set_min(&self, val: Self::T) {
+        if val.is_nan() {
+            return;
+        }
+        let mut current = self.inner.load(Ordering::Relaxed);
+        loop {
+            if u32_to_f32(current) <= val {
+                return;
+            }
+            match self.inner.compare_exchange_weak(
+                current,
+                f32_to_u32(val),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(observed) => current = observed,
+            }
+        }
+    }
 }
 
 /// A atomic signed integer.
+#[cfg(target_has_atomic = "64")]
 pub struct AtomicI64 {
     inner: StdAtomicI64,
 }
 
+#[cfg(target_has_atomic = "64")]
 impl Atomic for AtomicI64 {
     type T = i64;
 
@@ -168,13 +434,124 @@ impl Atomic for AtomicI64 {
     fn dec_by(&self, delta: Self::T) {
         self.inner.fetch_sub(delta, Ordering::Relaxed);
     }
+
+    #[inline]
+    fn set_max(&self, val: Self::T) {
+        self.inner.fetch_max(val, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn set_min(&self, val: Self::T) {
+        self.inner.fetch_min(val, Ordering::Relaxed);
+    }
+}
+
+/// A lock-based fallback for `AtomicI64` on targets without a native 64-bit
+/// atomic. See [`AtomicF64`] for the tradeoffs of this backend.
+#[cfg(not(target_has_atomic = "64"))]
+pub struct AtomicI64 {
+    inner: RwLock<i64>,
+}
+
+#[cfg(not(target_has_atomic = "64"))]
+impl Atomic for AtomicI64 {
+    type T = i64;
+
+    fn new(val: Self::T) -> AtomicI64 {
+        AtomicI64 {
+            inner: RwLock::new(val),
+        }
+    }
+
+    #[inline]
+    fn set(&self, val: Self::T) {
+        *self.inner.write().unwrap() = val;
+    }
+
+    #[inline]
+    fn get(&self) -> Self::T {
+        *self.inner.read().unwrap()
+    }
+
+    #[inline]
+    fn inc_by(&self, delta: Self::T) {
+        *self.inner.write().unwrap() += delta;
+    }
+
+    #[inline]
+    fn dec_by(&self, delta: Self::T) {
+        *self.inner.write().unwrap() -= delta;
+    }
+
+    #[inline]
+    fn set_max(&self, val: Self::T) {
+        let mut guard = self.inner.write().unwrap();
+        if val > *guard {
+            *guard = val;
+        }
+    }
+
+    #[inline]
+    fn set_min(&self, val: Self::T) {
+        let mut guard = self.inner.write().unwrap();
+        if val < *guard {
+            *guard = val;
+        }
+    }
+}
+
+/// A atomic 32-bit signed integer.
+pub struct AtomicI32 {
+    inner: StdAtomicI32,
+}
+
+impl Atomic for AtomicI32 {
+    type T = i32;
+
+    fn new(val: Self::T) -> AtomicI32 {
+        AtomicI32 {
+            inner: StdAtomicI32::new(val),
+        }
+    }
+
+    #[inline]
+    fn set(&self, val: Self::T) {
+        self.inner.store(val, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn get(&self) -> Self::T {
+        self.inner.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn inc_by(&self, delta: Self::T) {
+        self.inner.fetch_add(delta, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn dec_by(&self, delta: Self::T) {
+        self.inner.fetch_sub(delta, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn set_max(&self, val: Self::T) {
+        self.inner.fetch_max(val, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn set_min(&self, val: Self::T) {
+        self.inner.fetch_min(val, Ordering::Relaxed);
+    }
 }
 
 /// A atomic unsigned integer.
+#[cfg(target_has_atomic = "64")]
 pub struct AtomicU64 {
     inner: StdAtomicU64,
 }
 
+#[cfg(target_has_atomic = "64")]
 impl Atomic for AtomicU64 {
     type T = u64;
 
@@ -203,6 +580,276 @@ impl Atomic for AtomicU64 {
     fn dec_by(&self, delta: Self::T) {
         self.inner.fetch_sub(delta, Ordering::Relaxed);
     }
+
+    #[inline]
+    fn set_max(&self, val: Self::T) {
+        self.inner.fetch_max(val, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn set_min(&self, val: Self::T) {
+        self.inner.fetch_min(val, Ordering::Relaxed);
+    }
+}
+
+/// A lock-based fallback for `AtomicU64` on targets without a native 64-bit
+/// atomic. See [`AtomicF64`] for the tradeoffs of this backend.
+#[cfg(not(target_has_atomic = "64"))]
+pub struct AtomicU64 {
+    inner: RwLock<u64>,
+}
+
+#[cfg(not(target_has_atomic = "64"))]
+impl Atomic for AtomicU64 {
+    type T = u64;
+
+    fn new(val: Self::T) -> AtomicU64 {
+        AtomicU64 {
+            inner: RwLock::new(val),
+        }
+    }
+
+    #[inline]
+    fn set(&self, val: Self::T) {
+        *self.inner.write().unwrap() = val;
+    }
+
+    #[inline]
+    fn get(&self) -> Self::T {
+        *self.inner.read().unwrap()
+    }
+
+    #[inline]
+    fn inc_by(&self, delta: Self::T) {
+        *self.inner.write().unwrap() += delta;
+    }
+
+    #[inline]
+    fn dec_by(&self, delta: Self::T) {
+        *self.inner.write().unwrap() -= delta;
+    }
+
+    #[inline]
+    fn set_max(&self, val: Self::T) {
+        let mut guard = self.inner.write().unwrap();
+        if val > *guard {
+            *guard = val;
+        }
+    }
+
+    #[inline]
+    fn set_min(&self, val: Self::T) {
+        let mut guard = self.inner.write().unwrap();
+        if val < *guard {
+            *guard = val;
+        }
+    }
+}
+
+/// A atomic 32-bit unsigned integer.
+pub struct AtomicU32 {
+    inner: StdAtomicU32,
+}
+
+impl Atomic for AtomicU32 {
+    type T = u32;
+
+    fn new(val: Self::T) -> AtomicU32 {
+        AtomicU32 {
+            inner: StdAtomicU32::new(val),
+        }
+    }
+
+    #[inline]
+    fn set(&self, val: Self::T) {
+        self.inner.store(val, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn get(&self) -> Self::T {
+        self.inner.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn inc_by(&self, delta: Self::T) {
+        self.inner.fetch_add(delta, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn dec_by(&self, delta: Self::T) {
+        self.inner.fetch_sub(delta, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn set_max(&self, val: Self::T) {
+        self.inner.fetch_max(val, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn set_min(&self, val: Self::T) {
+        self.inner.fetch_min(val, Ordering::Relaxed);
+    }
+}
+
+/// A generic atomic wrapper for arbitrary `Copy` values, used for
+/// non-arithmetic "state-set" metrics (e.g. a service `Up`/`Degraded`/`Down`
+/// state) that the numeric-only [`Atomic`] trait and its [`Number`] bound
+/// cannot represent. This is a parallel abstraction to `Atomic`, not a
+/// replacement for it.
+///
+/// When `size_of::<T>()` matches a native integer width (1, 2, or 4 bytes,
+/// plus 8 bytes on targets with `target_has_atomic = "64"`), `T` is
+/// bit-copied into the corresponding standard atomic and every operation is
+/// lock-free. For any other size, a small spinlock (an `AtomicBool` guard)
+/// protects a `Cell<T>` instead.
+///
+/// `T` must be `Copy + 'static` with no padding whose bits could vary
+/// between otherwise-equal values, since the native-width path moves `T`
+/// in and out by reinterpreting its bits wholesale.
+pub struct GenericAtomic<T: Copy + 'static> {
+    repr: GenericRepr<T>,
+}
+
+enum GenericRepr<T: Copy + 'static> {
+    U8(StdAtomicU8),
+    U16(StdAtomicU16),
+    U32(StdAtomicU32),
+    #[cfg(target_has_atomic = "64")]
+    U64(StdAtomicU64),
+    Locked(AtomicBool, Cell<T>),
+}
+
+// SAFETY: the native-width variants only ever touch their inner standard
+// atomic, and the `Locked` variant only ever touches its `Cell` while
+// holding the spinlock. Every operation hands copies of `T` to the calling
+// thread, so `GenericAtomic<T>` is `Send` and `Sync` whenever `T` is `Send`.
+unsafe impl<T: Copy + Send + 'static> Send for GenericAtomic<T> {}
+unsafe impl<T: Copy + Send + 'static> Sync for GenericAtomic<T> {}
+
+impl<T: Copy + 'static> GenericAtomic<T> {
+    /// Create a new `GenericAtomic` holding `val`.
+    pub fn new(val: T) -> GenericAtomic<T> {
+        let repr = match size_of::<T>() {
+            1 => GenericRepr::U8(StdAtomicU8::new(unsafe { transmute_copy(&val) })),
+            2 => GenericRepr::U16(StdAtomicU16::new(unsafe { transmute_copy(&val) })),
+            4 => GenericRepr::U32(StdAtomicU32::new(unsafe { transmute_copy(&val) })),
+            #[cfg(target_has_atomic = "64")]
+            8 => GenericRepr::U64(StdAtomicU64::new(unsafe { transmute_copy(&val) })),
+            _ => GenericRepr::Locked(AtomicBool::new(false), Cell::new(val)),
+        };
+        GenericAtomic { repr }
+    }
+
+    /// Get the current value.
+    #[inline]
+    pub fn get(&self) -> T {
+        match &self.repr {
+            GenericRepr::U8(a) => unsafe { transmute_copy(&a.load(Ordering::Acquire)) },
+            GenericRepr::U16(a) => unsafe { transmute_copy(&a.load(Ordering::Acquire)) },
+            GenericRepr::U32(a) => unsafe { transmute_copy(&a.load(Ordering::Acquire)) },
+            #[cfg(target_has_atomic = "64")]
+            GenericRepr::U64(a) => unsafe { transmute_copy(&a.load(Ordering::Acquire)) },
+            GenericRepr::Locked(guard, cell) => {
+                self.spin_lock(guard);
+                let val = cell.get();
+                guard.store(false, Ordering::Release);
+                val
+            }
+        }
+    }
+
+    /// Set the value to the provided value.
+    #[inline]
+    pub fn set(&self, val: T) {
+        self.swap(val);
+    }
+
+    /// Set the value to the provided value, returning the previous value.
+    #[inline]
+    pub fn swap(&self, val: T) -> T {
+        match &self.repr {
+            GenericRepr::U8(a) => unsafe {
+                transmute_copy(&a.swap(transmute_copy(&val), Ordering::AcqRel))
+            },
+            GenericRepr::U16(a) => unsafe {
+                transmute_copy(&a.swap(transmute_copy(&val), Ordering::AcqRel))
+            },
+            GenericRepr::U32(a) => unsafe {
+                transmute_copy(&a.swap(transmute_copy(&val), Ordering::AcqRel))
+            },
+            #[cfg(target_has_atomic = "64")]
+            GenericRepr::U64(a) => unsafe {
+                transmute_copy(&a.swap(transmute_copy(&val), Ordering::AcqRel))
+            },
+            GenericRepr::Locked(guard, cell) => {
+                self.spin_lock(guard);
+                let old = cell.replace(val);
+                guard.store(false, Ordering::Release);
+                old
+            }
+        }
+    }
+
+    /// Atomically set the value to `new` if it currently equals `current`.
+    /// Returns the previous value either way, mirroring
+    /// `std::sync::atomic`'s `compare_exchange`.
+    pub fn compare_exchange(&self, current: T, new: T) -> Result<T, T>
+    where
+        T: PartialEq,
+    {
+        match &self.repr {
+            GenericRepr::U8(a) => {
+                let current_bits = unsafe { transmute_copy(&current) };
+                let new_bits = unsafe { transmute_copy(&new) };
+                a.compare_exchange(current_bits, new_bits, Ordering::AcqRel, Ordering::Acquire)
+                    .map(|v| unsafe { transmute_copy(&v) })
+                    .map_err(|v| unsafe { transmute_copy(&v) })
+            }
+            GenericRepr::U16(a) => {
+                let current_bits = unsafe { transmute_copy(&current) };
+                let new_bits = unsafe { transmute_copy(&new) };
+                a.compare_exchange(current_bits, new_bits, Ordering::AcqRel, Ordering::Acquire)
+                    .map(|v| unsafe { transmute_copy(&v) })
+                    .map_err(|v| unsafe { transmute_copy(&v) })
+            }
+            GenericRepr::U32(a) => {
+                let current_bits = unsafe { transmute_copy(&current) };
+                let new_bits = unsafe { transmute_copy(&new) };
+                a.compare_exchange(current_bits, new_bits, Ordering::AcqRel, Ordering::Acquire)
+                    .map(|v| unsafe { transmute_copy(&v) })
+                    .map_err(|v| unsafe { transmute_copy(&v) })
+            }
+            #[cfg(target_has_atomic = "64")]
+            GenericRepr::U64(a) => {
+                let current_bits = unsafe { transmute_copy(&current) };
+                let new_bits = unsafe { transmute_copy(&new) };
+                a.compare_exchange(current_bits, new_bits, Ordering::AcqRel, Ordering::Acquire)
+                    .map(|v| unsafe { transmute_copy(&v) })
+                    .map_err(|v| unsafe { transmute_copy(&v) })
+            }
+            GenericRepr::Locked(guard, cell) => {
+                self.spin_lock(guard);
+                let existing = cell.get();
+                let result = if existing == current {
+                    cell.set(new);
+                    Ok(existing)
+                } else {
+                    Err(existing)
+                };
+                guard.store(false, Ordering::Release);
+                result
+            }
+        }
+    }
+
+    /// Spin until `guard` is uncontended, leaving it locked (`true`) on return.
+    #[inline]
+    fn spin_lock(&self, guard: &AtomicBool) {
+        while guard
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {}
+    }
 }
 
 #[cfg(test)]
@@ -240,4 +887,215 @@ mod test {
         au64.inc_by(123);
         assert_eq!(au64.get(), 123);
     }
+
+    #[test]
+    fn test_atomic_f32() {
+        let table: Vec<f32> = vec![0.0, 1.0, PI as f32, f32::MIN, f32::MAX];
+
+        for f in table {
+            assert!((f - AtomicF32::new(f).get()).abs() < EPSILON as f32);
+        }
+    }
+
+    #[test]
+    fn test_atomic_i32() {
+        let ai32 = AtomicI32::new(0);
+        assert_eq!(ai32.get(), 0);
+
+        ai32.inc_by(1);
+        assert_eq!(ai32.get(), 1);
+
+        ai32.inc_by(-5);
+        assert_eq!(ai32.get(), -4);
+    }
+
+    #[test]
+    fn test_atomic_u32() {
+        let au32 = AtomicU32::new(0);
+        assert_eq!(au32.get(), 0);
+
+        au32.inc_by(123);
+        assert_eq!(au32.get(), 123);
+    }
+
+    #[test]
+    fn test_set_max_set_min() {
+        let ai64 = AtomicI64::new(5);
+        ai64.set_max(3);
+        assert_eq!(ai64.get(), 5);
+        ai64.set_max(8);
+        assert_eq!(ai64.get(), 8);
+        ai64.set_min(10);
+        assert_eq!(ai64.get(), 8);
+        ai64.set_min(2);
+        assert_eq!(ai64.get(), 2);
+
+        let af64 = AtomicF64::new(5.0);
+        af64.set_max(3.0);
+        assert!((af64.get() - 5.0).abs() < EPSILON);
+        af64.set_max(8.0);
+        assert!((af64.get() - 8.0).abs() < EPSILON);
+        af64.set_min(10.0);
+        assert!((af64.get() - 8.0).abs() < EPSILON);
+        af64.set_min(2.0);
+        assert!((af64.get() - 2.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_set_max_set_min_nan_is_ignored() {
+        let af64 = AtomicF64::new(5.0);
+        af64.set_max(f64::NAN);
+        assert!((af64.get() - 5.0).abs() < EPSILON);
+        af64.set_min(f64::NAN);
+        assert!((af64.get() - 5.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_atomic_f64_inc_by_contended() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let af64 = Arc::new(AtomicF64::new(0.0));
+        let handles: Vec<_> = (0..50)
+            .map(|_| {
+                let af64 = Arc::clone(&af64);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        af64.inc_by(1.0);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!((af64.get() - 50_000.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_atomic_f32_inc_by_contended() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let af32 = Arc::new(AtomicF32::new(0.0));
+        let handles: Vec<_> = (0..50)
+            .map(|_| {
+                let af32 = Arc::clone(&af32);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        af32.inc_by(1.0);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!((af32.get() - 50_000.0).abs() < EPSILON as f32);
+    }
+
+    #[test]
+    fn test_set_max_contended() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let ai64 = Arc::new(AtomicI64::new(0));
+        let handles: Vec<_> = (1..=50)
+            .map(|i| {
+                let ai64 = Arc::clone(&ai64);
+                thread::spawn(move || ai64.set_max(i))
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(ai64.get(), 50);
+    }
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    enum ServiceState {
+        Up,
+        Degraded,
+        Down,
+    }
+
+    #[test]
+    fn test_generic_atomic_native_width() {
+        let state = GenericAtomic::new(ServiceState::Up);
+        assert_eq!(state.get(), ServiceState::Up);
+
+        state.set(ServiceState::Down);
+        assert_eq!(state.get(), ServiceState::Down);
+
+        let old = state.swap(ServiceState::Degraded);
+        assert_eq!(old, ServiceState::Down);
+        assert_eq!(state.get(), ServiceState::Degraded);
+
+        assert_eq!(
+            state.compare_exchange(ServiceState::Degraded, ServiceState::Up),
+            Ok(ServiceState::Degraded)
+        );
+        assert_eq!(state.get(), ServiceState::Up);
+
+        assert_eq!(
+            state.compare_exchange(ServiceState::Degraded, ServiceState::Down),
+            Err(ServiceState::Up)
+        );
+        assert_eq!(state.get(), ServiceState::Up);
+    }
+
+    #[test]
+    fn test_generic_atomic_spinlock_fallback() {
+        #[derive(Copy, Clone, PartialEq, Debug)]
+        struct Oversized([u64; 3]);
+
+        let val = GenericAtomic::new(Oversized([1, 2, 3]));
+        assert_eq!(val.get(), Oversized([1, 2, 3]));
+
+        val.set(Oversized([4, 5, 6]));
+        assert_eq!(val.get(), Oversized([4, 5, 6]));
+    }
+
+    // The tests above already exercise whichever 64-bit backend this target
+    // selects (native lock-free atomics or the `RwLock`-based fallback),
+    // since `AtomicF64`/`AtomicI64`/`AtomicU64` expose the same public API
+    // either way. This module additionally locks in the fallback's own
+    // behavior on targets that lack native 64-bit atomics.
+    #[cfg(not(target_has_atomic = "64"))]
+    mod lock_backend {
+        use super::*;
+
+        #[test]
+        fn test_lock_backend_f64() {
+            let af64 = AtomicF64::new(1.5);
+            assert!((af64.get() - 1.5).abs() < EPSILON);
+
+            af64.inc_by(0.5);
+            assert!((af64.get() - 2.0).abs() < EPSILON);
+
+            af64.dec_by(1.0);
+            assert!((af64.get() - 1.0).abs() < EPSILON);
+        }
+
+        #[test]
+        fn test_lock_backend_i64() {
+            let ai64 = AtomicI64::new(0);
+            ai64.inc_by(5);
+            ai64.dec_by(2);
+            assert_eq!(ai64.get(), 3);
+        }
+
+        #[test]
+        fn test_lock_backend_u64() {
+            let au64 = AtomicU64::new(0);
+            au64.inc_by(5);
+            assert_eq!(au64.get(), 5);
+        }
+    }
 }